@@ -1,4 +1,4 @@
-use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, Utc};
+use chrono::{DateTime, Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike, Utc};
 use crossterm::event::{self, Event, KeyCode};
 use ratatui::widgets::Paragraph;
 use ratatui::{
@@ -6,6 +6,7 @@ use ratatui::{
     widgets::{Block, Borders, List, ListItem, Tabs},
 };
 use rusqlite::Connection;
+use serde::Deserialize;
 use std::cmp::Reverse;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter};
@@ -14,6 +15,137 @@ use std::io::{self};
 use std::path::Path;
 
 const KNOWLEDGE_DB: &str = "/Users/max/Library/Application Support/Knowledge/knowledgeC.db";
+const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Day the week is considered to start on, used when bucketing daily usage into weeks.
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum WeekDay {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl WeekDay {
+    fn num_days_from_monday(self) -> i64 {
+        match self {
+            WeekDay::Monday => 0,
+            WeekDay::Tuesday => 1,
+            WeekDay::Wednesday => 2,
+            WeekDay::Thursday => 3,
+            WeekDay::Friday => 4,
+            WeekDay::Saturday => 5,
+            WeekDay::Sunday => 6,
+        }
+    }
+}
+
+/// Number of days `weekday` falls after the configured start of the week.
+fn days_since_week_start(weekday: chrono::Weekday, week_start: WeekDay) -> i64 {
+    (weekday.num_days_from_monday() as i64 - week_start.num_days_from_monday()).rem_euclid(7)
+}
+
+/// Converts `naive` (interpreted as wall-clock time in the local timezone) into a
+/// `DateTime<Local>` without panicking on a DST edge: picks the earlier instant when
+/// the time is ambiguous ("fall back"), and falls back to treating it as UTC when the
+/// time doesn't exist at all ("spring forward").
+fn local_datetime(naive: NaiveDateTime) -> DateTime<Local> {
+    naive
+        .and_local_timezone(Local)
+        .earliest()
+        .unwrap_or_else(|| Local.from_utc_datetime(&naive))
+}
+
+/// How `analyze_usage` decides what counts as a break in a day's usage.
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+enum BreakDetectionMode {
+    /// Only the gaps between recorded sessions count as breaks.
+    SessionGaps,
+    /// In addition to session gaps, idle time before `first_usage` and after
+    /// `last_usage` within working hours is reported as a break.
+    WorkingHoursIdle,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+struct Settings {
+    knowledge_db_path: String,
+    week_start: WeekDay,
+    daily_goal_hours: f64,
+    weekly_goal_hours: f64,
+    export_privacy_mode: bool,
+    break_threshold_minutes: i64,
+    break_detection_mode: BreakDetectionMode,
+    working_hours_start: u32,
+    working_hours_end: u32,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            knowledge_db_path: KNOWLEDGE_DB.to_string(),
+            week_start: WeekDay::Monday,
+            daily_goal_hours: DEFAULT_DAILY_GOAL_HOURS,
+            weekly_goal_hours: DEFAULT_WEEKLY_GOAL_HOURS,
+            export_privacy_mode: false,
+            break_threshold_minutes: 10,
+            break_detection_mode: BreakDetectionMode::SessionGaps,
+            working_hours_start: 9,
+            working_hours_end: 18,
+        }
+    }
+}
+
+impl Settings {
+    /// Loads settings from `<config dir>/screen-time-stats/config.toml`, falling back to
+    /// defaults if the file is missing or fails to parse.
+    fn load() -> Settings {
+        let Some(config_path) = dirs::config_dir().map(|dir| dir.join("screen-time-stats").join(CONFIG_FILE_NAME)) else {
+            return Settings::default();
+        };
+
+        let Ok(contents) = fs::read_to_string(&config_path) else {
+            return Settings::default();
+        };
+
+        let settings: Settings = toml::from_str(&contents).unwrap_or_else(|e| {
+            eprintln!(
+                "Failed to parse config at {}: {e}. Using defaults.",
+                config_path.display()
+            );
+            Settings::default()
+        });
+
+        settings.validated()
+    }
+
+    /// Resets an out-of-range `working_hours_start` or `working_hours_end` (an hour outside
+    /// 0-23) to its default instead of letting it panic deep in `analyze_usage`'s
+    /// `and_hms_opt`. No other field is checked.
+    fn validated(mut self) -> Settings {
+        let defaults = Settings::default();
+        if self.working_hours_start > 23 {
+            eprintln!(
+                "Invalid working_hours_start {} (must be 0-23); using default {}.",
+                self.working_hours_start, defaults.working_hours_start
+            );
+            self.working_hours_start = defaults.working_hours_start;
+        }
+        if self.working_hours_end > 23 {
+            eprintln!(
+                "Invalid working_hours_end {} (must be 0-23); using default {}.",
+                self.working_hours_end, defaults.working_hours_end
+            );
+            self.working_hours_end = defaults.working_hours_end;
+        }
+        self
+    }
+}
 
 #[derive(Debug)]
 struct UsageData {
@@ -26,8 +158,8 @@ struct UsageData {
     //created_at: DateTime<Utc>,
     //tz: f64,
 }
-fn query_database() -> anyhow::Result<Vec<UsageData>> {
-    let db_path = KNOWLEDGE_DB;
+fn query_database(settings: &Settings) -> anyhow::Result<Vec<UsageData>> {
+    let db_path = &settings.knowledge_db_path;
 
     if !Path::new(&db_path).exists() {
         eprintln!("Could not find knowledgeC.db at {}.", db_path);
@@ -104,6 +236,141 @@ fn format_duration(duration: &Duration) -> String {
     }
 }
 
+const MIN_PER_DAY: u32 = 24 * 60;
+const HEATMAP_SLICE_MINUTES: u32 = 30;
+const CHART_BLOCK_MINUTES: usize = 15;
+const DEFAULT_DAILY_GOAL_HOURS: f64 = 6.0;
+const DEFAULT_WEEKLY_GOAL_HOURS: f64 = 30.0;
+
+/// Number of `block_minutes`-sized blocks needed to represent `hours`.
+fn hour_blocks(hours: f64, block_minutes: usize) -> usize {
+    (hours * 60.0) as usize / block_minutes
+}
+
+/// Renders `used_hours` as a row of blocks (`block_minutes` each): green up to
+/// `goal_hours`, red for any overflow, followed by a "{used}/{goal}" summary colored
+/// the same way.
+fn render_goal_bar(used_hours: f64, goal_hours: f64, block_minutes: usize) -> Line<'static> {
+    let used_blocks = hour_blocks(used_hours, block_minutes);
+    let goal_blocks = hour_blocks(goal_hours, block_minutes);
+    let under_goal_blocks = used_blocks.min(goal_blocks);
+    let over_goal_blocks = used_blocks.saturating_sub(goal_blocks);
+
+    let summary_color = if used_hours > goal_hours {
+        Color::Red
+    } else {
+        Color::Green
+    };
+
+    Line::from(vec![
+        Span::styled(
+            "█".repeat(under_goal_blocks),
+            Style::default().fg(Color::Green),
+        ),
+        Span::styled("█".repeat(over_goal_blocks), Style::default().fg(Color::Red)),
+        Span::raw(" "),
+        Span::styled(
+            format!("{:.1}/{:.1}", used_hours, goal_hours),
+            Style::default().fg(summary_color),
+        ),
+    ])
+}
+
+/// How busy a single heatmap slice was, relative to the busiest slice of the row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Intensity {
+    None,
+    Low,
+    Medium,
+    High,
+}
+
+impl Intensity {
+    fn from_ratio(ratio: f64) -> Self {
+        if ratio <= 0.0 {
+            Intensity::None
+        } else if ratio <= 0.33 {
+            Intensity::Low
+        } else if ratio <= 0.66 {
+            Intensity::Medium
+        } else {
+            Intensity::High
+        }
+    }
+
+    fn glyph(self) -> char {
+        match self {
+            Intensity::None => ' ',
+            Intensity::Low => '░',
+            Intensity::Medium => '▒',
+            Intensity::High => '█',
+        }
+    }
+}
+
+/// Buckets the active seconds of each session into fixed-width `slice_minutes` time
+/// slices, keyed by the slice's start (minute rounded down to the nearest boundary).
+fn bucket_by_slice(
+    sessions: &[(DateTime<Local>, DateTime<Local>)],
+    slice_minutes: u32,
+) -> HashMap<NaiveDateTime, u64> {
+    let mut buckets: HashMap<NaiveDateTime, u64> = HashMap::new();
+
+    for (start, end) in sessions {
+        let start = start.naive_local();
+        let end = end.naive_local();
+
+        let minute = start.minute();
+        let slice_start_minute = minute - (minute % slice_minutes);
+        let mut slice_start = start
+            .date()
+            .and_hms_opt(start.hour(), slice_start_minute, 0)
+            .unwrap();
+
+        while slice_start < end {
+            let slice_end = slice_start + Duration::minutes(slice_minutes as i64);
+            let overlap_start = slice_start.max(start);
+            let overlap_end = slice_end.min(end);
+            if overlap_end > overlap_start {
+                let seconds = (overlap_end - overlap_start).num_seconds() as u64;
+                *buckets.entry(slice_start).or_insert(0) += seconds;
+            }
+            slice_start = slice_end;
+        }
+    }
+
+    buckets
+}
+
+/// Renders a session list as one glyph per `slice_minutes` slice of the day, e.g. a
+/// 30 minute slice gives a 48-character row spanning midnight to midnight.
+fn render_heatmap_row(sessions: &[(DateTime<Local>, DateTime<Local>)], slice_minutes: u32) -> String {
+    let buckets = bucket_by_slice(sessions, slice_minutes);
+    let slot_count = (MIN_PER_DAY / slice_minutes) as usize;
+
+    let mut by_slot = vec![0u64; slot_count];
+    for (slice_start, seconds) in &buckets {
+        let minute_of_day = slice_start.hour() * 60 + slice_start.minute();
+        let slot = (minute_of_day / slice_minutes) as usize;
+        if slot < slot_count {
+            by_slot[slot] += seconds;
+        }
+    }
+
+    let max_seconds = by_slot.iter().copied().max().unwrap_or(0) as f64;
+    by_slot
+        .iter()
+        .map(|&seconds| {
+            let ratio = if max_seconds > 0.0 {
+                seconds as f64 / max_seconds
+            } else {
+                0.0
+            };
+            Intensity::from_ratio(ratio).glyph()
+        })
+        .collect()
+}
+
 #[derive(Debug, Default)]
 struct DailyUsage {
     total_usage: i64,
@@ -112,11 +379,19 @@ struct DailyUsage {
     per_app_usage: HashMap<String, i64>,
     breaks: Vec<(DateTime<Local>, DateTime<Local>, Duration)>, // Global break intervals per day with duration
     net_active_time: Duration,
+    sessions: Vec<(DateTime<Local>, DateTime<Local>)>,
+    has_data: bool,
+    break_threshold: Duration,
+    focus_score: f64,
 }
 
 impl Display for DailyUsage {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         writeln!(f, "Date: {}", self.first_usage.date_naive())?;
+        if !self.has_data {
+            writeln!(f, "  No activity recorded")?;
+            return Ok(());
+        }
         writeln!(
             f,
             "  Total Usage: {}",
@@ -129,6 +404,12 @@ impl Display for DailyUsage {
             "  Net Active Hours: {}",
             format_duration(&self.net_active_time)
         )?;
+        writeln!(
+            f,
+            "  Break Threshold: {}",
+            format_duration(&self.break_threshold)
+        )?;
+        writeln!(f, "  Focus Score: {:.0}%", self.focus_score * 100.0)?;
         writeln!(f, "  Per App Usage:")?;
         for (app, usage_time) in &self.per_app_usage {
             writeln!(
@@ -199,7 +480,8 @@ impl Display for WeeklyUsage {
     }
 }
 
-fn analyze_usage(data: Vec<UsageData>) -> Vec<(NaiveDate, DailyUsage)> {
+fn analyze_usage(data: Vec<UsageData>, settings: &Settings) -> Vec<(NaiveDate, DailyUsage)> {
+    let break_threshold = Duration::minutes(settings.break_threshold_minutes);
     let mut daily_usage: HashMap<NaiveDate, DailyUsage> = HashMap::new();
 
     for entry in &data {
@@ -210,6 +492,7 @@ fn analyze_usage(data: Vec<UsageData>) -> Vec<(NaiveDate, DailyUsage)> {
         let daily_entry = daily_usage.entry(date).or_insert_with(|| DailyUsage {
             first_usage,
             last_usage,
+            has_data: true,
             ..Default::default()
         });
 
@@ -247,15 +530,47 @@ fn analyze_usage(data: Vec<UsageData>) -> Vec<(NaiveDate, DailyUsage)> {
             let (_, prev_end) = sessions[i - 1];
             let (current_start, _) = sessions[i];
             let break_duration = current_start.signed_duration_since(prev_end);
-            if break_duration > Duration::minutes(10) {
+            if break_duration > break_threshold {
                 breaks.push((prev_end, current_start, break_duration));
                 total_break_duration += break_duration;
             }
         }
 
+        if settings.break_detection_mode == BreakDetectionMode::WorkingHoursIdle {
+            let working_start =
+                local_datetime(date.and_hms_opt(settings.working_hours_start, 0, 0).unwrap());
+            let working_end =
+                local_datetime(date.and_hms_opt(settings.working_hours_end, 0, 0).unwrap());
+
+            let idle_before_end = usage.first_usage.min(working_end);
+            let idle_before = idle_before_end.signed_duration_since(working_start);
+            if idle_before_end > working_start && idle_before > break_threshold {
+                breaks.push((working_start, idle_before_end, idle_before));
+            }
+
+            let idle_after_start = usage.last_usage.max(working_start);
+            let idle_after = working_end.signed_duration_since(idle_after_start);
+            if idle_after_start < working_end && idle_after > break_threshold {
+                breaks.push((idle_after_start, working_end, idle_after));
+            }
+        }
+
         usage.breaks = breaks;
         usage.net_active_time =
             usage.last_usage.signed_duration_since(usage.first_usage) - total_break_duration;
+        usage.focus_score = {
+            let total_span = usage
+                .last_usage
+                .signed_duration_since(usage.first_usage)
+                .num_seconds();
+            if total_span > 0 {
+                usage.net_active_time.num_seconds() as f64 / total_span as f64
+            } else {
+                0.0
+            }
+        };
+        usage.sessions = sessions;
+        usage.break_threshold = break_threshold;
     }
 
     let mut sorted_analysis: Vec<_> = daily_usage.into_iter().collect();
@@ -263,15 +578,53 @@ fn analyze_usage(data: Vec<UsageData>) -> Vec<(NaiveDate, DailyUsage)> {
     sorted_analysis
 }
 
-fn analyze_weekly_usage(daily_usage: &Vec<(NaiveDate, DailyUsage)>) -> Vec<(u32, WeeklyUsage)> {
-    let mut weekly_usage: HashMap<u32, WeeklyUsage> = HashMap::new();
-    let current_week = Local::now().iso_week().week();
+/// Fills every `NaiveDate` between the earliest and latest entry in `daily_usage` with
+/// a placeholder `DailyUsage` (`has_data: false`), so days the machine was off or
+/// unused don't silently vanish from the daily list, weekly aggregation, or charts.
+fn fill_date_gaps(daily_usage: Vec<(NaiveDate, DailyUsage)>) -> Vec<(NaiveDate, DailyUsage)> {
+    let Some(min_date) = daily_usage.iter().map(|(date, _)| *date).min() else {
+        return daily_usage;
+    };
+    let max_date = daily_usage.iter().map(|(date, _)| *date).max().unwrap();
+
+    let mut by_date: HashMap<NaiveDate, DailyUsage> = daily_usage.into_iter().collect();
+
+    let mut date = min_date;
+    while date <= max_date {
+        by_date.entry(date).or_insert_with(|| {
+            let midnight = local_datetime(date.and_hms_opt(0, 0, 0).unwrap());
+            DailyUsage {
+                first_usage: midnight,
+                last_usage: midnight,
+                has_data: false,
+                ..Default::default()
+            }
+        });
+        date += Duration::days(1);
+    }
+
+    let mut sorted_analysis: Vec<_> = by_date.into_iter().collect();
+    sorted_analysis.sort_by_key(|(date, _)| Reverse(*date));
+    sorted_analysis
+}
+
+fn analyze_weekly_usage(
+    daily_usage: &Vec<(NaiveDate, DailyUsage)>,
+    week_start: WeekDay,
+) -> Vec<(NaiveDate, WeeklyUsage)> {
+    // Group by `first_day` (the start of each custom week), not `iso_week()`: the ISO
+    // week number always flips on a Monday, so for any other `week_start` two
+    // consecutive days in the same custom week would otherwise land in different
+    // groups.
+    let mut weekly_usage: HashMap<NaiveDate, WeeklyUsage> = HashMap::new();
+    let today = Local::now().date_naive();
+    let current_week_first_day =
+        today - Duration::days(days_since_week_start(today.weekday(), week_start));
 
     for (date, usage) in daily_usage {
-        let week = date.iso_week().week();
-        let first_day = *date - Duration::days(date.weekday().num_days_from_monday() as i64);
-        let is_current_week = week == current_week;
-        let weekly_entry = weekly_usage.entry(week).or_insert_with(|| WeeklyUsage {
+        let first_day = *date - Duration::days(days_since_week_start(date.weekday(), week_start));
+        let is_current_week = first_day == current_week_first_day;
+        let weekly_entry = weekly_usage.entry(first_day).or_insert_with(|| WeeklyUsage {
             first_day,
             is_current_week,
             ..Default::default()
@@ -286,10 +639,138 @@ fn analyze_weekly_usage(daily_usage: &Vec<(NaiveDate, DailyUsage)>) -> Vec<(u32,
     }
 
     let mut sorted_analysis: Vec<_> = weekly_usage.into_iter().collect();
-    sorted_analysis.sort_by_key(|(week, _)| Reverse(*week));
+    sorted_analysis.sort_by_key(|(first_day, _)| Reverse(*first_day));
     sorted_analysis
 }
 
+const EXPORT_FILE_NAME: &str = "screen-time-report.html";
+const EXPORT_DAYS: usize = 30;
+
+/// Maps an app name to a coarse category label, used to anonymize per-app usage when
+/// exporting a report in privacy mode.
+fn categorize_app(app: &str) -> &'static str {
+    let app = app.to_lowercase();
+    if app.contains("safari") || app.contains("chrome") || app.contains("firefox") {
+        "Browser"
+    } else if app.contains("mail") || app.contains("message") || app.contains("slack")
+        || app.contains("teams") || app.contains("zoom")
+    {
+        "Communication"
+    } else if app.contains("code") || app.contains("xcode") || app.contains("terminal")
+        || app.contains("studio")
+    {
+        "Development"
+    } else if app.contains("music") || app.contains("tv") || app.contains("video")
+        || app.contains("game")
+    {
+        "Entertainment"
+    } else {
+        "Other"
+    }
+}
+
+/// Escapes the HTML-significant characters in `text` so it's safe to interpolate into
+/// the exported report, which may contain arbitrary app names from the system database.
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+/// CSS class name for an intensity grade, used to color calendar cells in the HTML report.
+fn intensity_css_class(intensity: Intensity) -> &'static str {
+    match intensity {
+        Intensity::None => "intensity-none",
+        Intensity::Low => "intensity-low",
+        Intensity::Medium => "intensity-medium",
+        Intensity::High => "intensity-high",
+    }
+}
+
+/// Renders the last `days` entries of `daily_analysis` as a single self-contained HTML
+/// file: a calendar-style grid with one cell per day, color-coded by usage against
+/// `settings.daily_goal_hours`. When `privacy_mode` is set, per-app names are replaced
+/// with their [`categorize_app`] category so the file can be shared without leaking
+/// which apps were used.
+fn export_html_report(
+    daily_analysis: &[(NaiveDate, DailyUsage)],
+    settings: &Settings,
+    days: usize,
+    privacy_mode: bool,
+) -> String {
+    let mut cells = String::new();
+
+    for (date, usage) in daily_analysis.iter().take(days) {
+        let hours = usage.total_usage as f64 / 3600.0;
+        let ratio = if settings.daily_goal_hours > 0.0 {
+            hours / settings.daily_goal_hours
+        } else {
+            0.0
+        };
+        let css_class = intensity_css_class(Intensity::from_ratio(ratio));
+
+        let mut top_apps: Vec<(&String, &i64)> = usage.per_app_usage.iter().collect();
+        top_apps.sort_by_key(|(_, usage)| Reverse(**usage));
+
+        let mut apps_html = String::new();
+        for (app, app_usage) in top_apps.iter().take(3) {
+            let label = if privacy_mode {
+                categorize_app(app).to_string()
+            } else {
+                (*app).clone()
+            };
+            apps_html.push_str(&format!(
+                "<li>{}: {}</li>",
+                escape_html(&label),
+                format_duration(&Duration::seconds(**app_usage))
+            ));
+        }
+
+        cells.push_str(&format!(
+            r#"<div class="cell {css_class}">
+  <div class="date">{date}</div>
+  <div class="total">{total}</div>
+  <div class="net">Net active: {net}</div>
+  <ul class="apps">{apps_html}</ul>
+</div>
+"#,
+            css_class = css_class,
+            date = date,
+            total = format_duration(&Duration::seconds(usage.total_usage)),
+            net = format_duration(&usage.net_active_time),
+            apps_html = apps_html,
+        ));
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Screen Time Report</title>
+<style>
+  body {{ font-family: sans-serif; background: #1e1e1e; color: #eee; }}
+  .grid {{ display: grid; grid-template-columns: repeat(7, 1fr); gap: 8px; }}
+  .cell {{ border-radius: 6px; padding: 8px; background: #2a2a2a; }}
+  .date {{ font-weight: bold; }}
+  .apps {{ margin: 4px 0 0; padding-left: 16px; font-size: 0.85em; }}
+  .intensity-none {{ background: #2a2a2a; }}
+  .intensity-low {{ background: #1b4332; }}
+  .intensity-medium {{ background: #b08900; }}
+  .intensity-high {{ background: #7a1f1f; }}
+</style>
+</head>
+<body>
+<h1>Screen Time Report</h1>
+<div class="grid">
+{cells}</div>
+</body>
+</html>
+"#,
+        cells = cells,
+    )
+}
+
 fn generate_entries_and_details<U: Display, V: Display, F>(
     analysis: &Vec<(U, V)>,
     selected_index: usize,
@@ -319,14 +800,19 @@ where
     (entries, detail)
 }
 
+const TAB_COUNT: usize = 3;
+
 fn run_tui(
     daily_analysis: Vec<(NaiveDate, DailyUsage)>,
-    weekly_analysis: Vec<(u32, WeeklyUsage)>,
+    weekly_analysis: Vec<(NaiveDate, WeeklyUsage)>,
+    settings: &Settings,
 ) -> Result<(), io::Error> {
     let mut terminal = ratatui::init();
 
     let mut selected_tab = 0;
     let mut selected_index = 0;
+    let mut privacy_mode = settings.export_privacy_mode;
+    let mut export_status: Option<String> = None;
 
     loop {
         terminal.draw(|frame| {
@@ -336,21 +822,84 @@ fn run_tui(
                 .constraints([Constraint::Length(3), Constraint::Min(0)].as_ref())
                 .split(frame.area());
 
-            let titles = vec!["Daily Analysis", "Weekly Analysis"];
+            let titles = vec!["Daily Analysis", "Weekly Analysis", "Heatmap"];
+            let tabs_title = format!(
+                "Analysis ('e' to export HTML, 'p' to toggle privacy mode [{}]){}",
+                if privacy_mode { "on" } else { "off" },
+                export_status
+                    .as_ref()
+                    .map(|status| format!(" — {status}"))
+                    .unwrap_or_default()
+            );
             let tabs = Tabs::new(titles)
-                .block(Block::default().borders(Borders::ALL).title("Analysis"))
+                .block(Block::default().borders(Borders::ALL).title(tabs_title))
                 .select(selected_tab)
                 .highlight_style(Style::default().fg(Color::Yellow));
             frame.render_widget(tabs, chunks[0]);
 
-            let (items, details) = match selected_tab {
-                0 => generate_entries_and_details(&daily_analysis, selected_index, |key, value| {
-                    key.to_string()
-                }),
+            let (items, details): (Vec<ListItem>, Text) = match selected_tab {
+                0 => {
+                    let (entries, detail) = generate_entries_and_details(
+                        &daily_analysis,
+                        selected_index,
+                        |key, _| key.to_string(),
+                    );
+                    let mut lines = Vec::new();
+                    if let Some((_, usage)) = daily_analysis.get(selected_index) {
+                        lines.push(render_goal_bar(
+                            usage.total_usage as f64 / 3600.0,
+                            settings.daily_goal_hours,
+                            CHART_BLOCK_MINUTES,
+                        ));
+                        lines.push(Line::raw(""));
+                    }
+                    lines.extend(detail.lines().map(|line| Line::raw(line.to_string())));
+                    (entries, Text::from(lines))
+                }
+                1 => {
+                    let (entries, detail) = generate_entries_and_details(
+                        &weekly_analysis,
+                        selected_index,
+                        |_, value| {
+                            format!(
+                                "Week {} (Starting {})",
+                                value.first_day.iso_week().week(),
+                                value.first_day
+                            )
+                        },
+                    );
+                    let mut lines = Vec::new();
+                    if let Some((_, usage)) = weekly_analysis.get(selected_index) {
+                        lines.push(render_goal_bar(
+                            usage.total_usage as f64 / 3600.0,
+                            settings.weekly_goal_hours,
+                            CHART_BLOCK_MINUTES,
+                        ));
+                        lines.push(Line::raw(""));
+                    }
+                    lines.extend(detail.lines().map(|line| Line::raw(line.to_string())));
+                    (entries, Text::from(lines))
+                }
                 _ => {
-                    generate_entries_and_details(&weekly_analysis, selected_index, |key, value| {
-                        format!("Week {} (Starting {})", key, value.first_day)
-                    })
+                    let (entries, _) =
+                        generate_entries_and_details(&daily_analysis, selected_index, |key, _| {
+                            key.to_string()
+                        });
+                    let detail = daily_analysis
+                        .get(selected_index)
+                        .map(|(date, usage)| {
+                            format!(
+                                "Date: {}\n  Time-of-day heatmap ({} min slices, 00:00-24:00):\n  {}",
+                                date,
+                                HEATMAP_SLICE_MINUTES,
+                                render_heatmap_row(&usage.sessions, HEATMAP_SLICE_MINUTES)
+                            )
+                        })
+                        .unwrap_or("No data available".to_string());
+                    (
+                        entries,
+                        Text::from(detail.lines().map(|line| Line::raw(line.to_string())).collect::<Vec<_>>()),
+                    )
                 }
             };
 
@@ -372,11 +921,11 @@ fn run_tui(
             if let Event::Key(key) = event {
                 match key.code {
                     KeyCode::Left => {
-                        selected_tab = 0;
+                        selected_tab = (selected_tab + TAB_COUNT - 1) % TAB_COUNT;
                         selected_index = 0;
                     }
                     KeyCode::Right => {
-                        selected_tab = 1;
+                        selected_tab = (selected_tab + 1) % TAB_COUNT;
                         selected_index = 0;
                     }
                     KeyCode::Up => {
@@ -386,13 +935,24 @@ fn run_tui(
                     }
                     KeyCode::Down => {
                         let max_index = match selected_tab {
-                            0 => daily_analysis.len().saturating_sub(1),
-                            _ => weekly_analysis.len().saturating_sub(1),
+                            1 => weekly_analysis.len().saturating_sub(1),
+                            _ => daily_analysis.len().saturating_sub(1),
                         };
                         if selected_index < max_index {
                             selected_index += 1;
                         }
                     }
+                    KeyCode::Char('e') => {
+                        let html =
+                            export_html_report(&daily_analysis, settings, EXPORT_DAYS, privacy_mode);
+                        export_status = Some(match fs::write(EXPORT_FILE_NAME, html) {
+                            Ok(()) => format!("Exported to {EXPORT_FILE_NAME}"),
+                            Err(e) => format!("Export failed: {e}"),
+                        });
+                    }
+                    KeyCode::Char('p') => {
+                        privacy_mode = !privacy_mode;
+                    }
                     KeyCode::Esc | KeyCode::Char('q') => break,
                     _ => {}
                 }
@@ -405,14 +965,104 @@ fn run_tui(
 }
 
 fn main() {
-    match query_database() {
+    let settings = Settings::load();
+
+    match query_database(&settings) {
         Ok(data) => {
-            let daily_analysis = analyze_usage(data);
-            let weekly_analysis = analyze_weekly_usage(&daily_analysis);
-            if let Err(e) = run_tui(daily_analysis, weekly_analysis) {
+            let daily_analysis = fill_date_gaps(analyze_usage(data, &settings));
+            let weekly_analysis = analyze_weekly_usage(&daily_analysis, settings.week_start);
+            if let Err(e) = run_tui(daily_analysis, weekly_analysis, &settings) {
                 eprintln!("TUI Error: {:?}", e);
             }
         }
         Err(e) => eprintln!("Error querying database: {:?}", e),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Weekday;
+
+    #[test]
+    fn bucket_by_slice_keeps_a_session_within_one_slice_in_a_single_bucket() {
+        let day = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let start = local_datetime(day.and_hms_opt(9, 5, 0).unwrap());
+        let end = local_datetime(day.and_hms_opt(9, 20, 0).unwrap());
+
+        let buckets = bucket_by_slice(&[(start, end)], 30);
+
+        let slice_start = day.and_hms_opt(9, 0, 0).unwrap();
+        assert_eq!(buckets.len(), 1);
+        assert_eq!(buckets.get(&slice_start), Some(&(15 * 60)));
+    }
+
+    #[test]
+    fn bucket_by_slice_splits_a_session_across_a_slice_boundary() {
+        let day = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let start = local_datetime(day.and_hms_opt(9, 45, 0).unwrap());
+        let end = local_datetime(day.and_hms_opt(10, 15, 0).unwrap());
+
+        let buckets = bucket_by_slice(&[(start, end)], 30);
+
+        let first_slice = day.and_hms_opt(9, 30, 0).unwrap();
+        let second_slice = day.and_hms_opt(10, 0, 0).unwrap();
+        assert_eq!(buckets.len(), 2);
+        assert_eq!(buckets.get(&first_slice), Some(&(15 * 60)));
+        assert_eq!(buckets.get(&second_slice), Some(&(15 * 60)));
+    }
+
+    #[test]
+    fn days_since_week_start_is_zero_on_the_configured_start_day() {
+        let week_starts = [
+            (WeekDay::Monday, Weekday::Mon),
+            (WeekDay::Tuesday, Weekday::Tue),
+            (WeekDay::Wednesday, Weekday::Wed),
+            (WeekDay::Thursday, Weekday::Thu),
+            (WeekDay::Friday, Weekday::Fri),
+            (WeekDay::Saturday, Weekday::Sat),
+            (WeekDay::Sunday, Weekday::Sun),
+        ];
+
+        for (week_start, start_weekday) in week_starts {
+            assert_eq!(days_since_week_start(start_weekday, week_start), 0);
+            assert_eq!(days_since_week_start(start_weekday.pred(), week_start), 6);
+            assert_eq!(days_since_week_start(start_weekday.succ(), week_start), 1);
+        }
+    }
+
+    #[cfg(unix)]
+    extern "C" {
+        fn tzset();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn local_datetime_does_not_panic_on_a_dst_ambiguous_time() {
+        // America/Sao_Paulo ended DST at midnight on 2019-02-17 by setting clocks back to
+        // 23:00 on 2019-02-16, so 23:30 that evening occurred twice and is ambiguous. A bare
+        // `.and_local_timezone(Local).unwrap()` panics here; `local_datetime` must instead
+        // resolve it (to the earlier instant).
+        let previous_tz = std::env::var("TZ").ok();
+        unsafe {
+            std::env::set_var("TZ", "America/Sao_Paulo");
+            tzset();
+        }
+
+        let ambiguous_time = NaiveDate::from_ymd_opt(2019, 2, 16)
+            .unwrap()
+            .and_hms_opt(23, 30, 0)
+            .unwrap();
+        let resolved = local_datetime(ambiguous_time);
+
+        unsafe {
+            match &previous_tz {
+                Some(tz) => std::env::set_var("TZ", tz),
+                None => std::env::remove_var("TZ"),
+            }
+            tzset();
+        }
+
+        assert_eq!(resolved.naive_local(), ambiguous_time);
+    }
+}